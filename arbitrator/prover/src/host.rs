@@ -10,12 +10,29 @@ use crate::{
 use arbutil::Color;
 
 /// Represents the internal hostio functions a module may have.
+/// Note: the order of the variants must match that documented in `add_internal_funcs`.
 #[repr(u64)]
 enum InternalFunc {
     WavmCallerLoad8,
     WavmCallerLoad32,
     WavmCallerStore8,
     WavmCallerStore32,
+    // 32-bit-addressed, wider accesses
+    WavmCallerLoad16,
+    WavmCallerLoad64,
+    WavmCallerStore16,
+    WavmCallerStore64,
+    // memory64: same widths, but addressed with an I64 pointer
+    WavmCallerLoad8_64,
+    WavmCallerLoad16_64,
+    WavmCallerLoad32_64,
+    WavmCallerLoad64_64,
+    WavmCallerStore8_64,
+    WavmCallerStore16_64,
+    WavmCallerStore32_64,
+    WavmCallerStore64_64,
+    // Only pushed when the module carries Stylus globals; must stay last so that every
+    // variant above keeps a fixed index regardless of whether these are present.
     UserGasLeft,
     UserGasStatus,
     UserGasSet,
@@ -24,130 +41,508 @@ enum InternalFunc {
 impl InternalFunc {
     fn ty(&self) -> FunctionType {
         use ArbValueType::*;
-        FunctionType::new(vec![I32], vec![I32])
+        use InternalFunc::*;
+        match self {
+            WavmCallerLoad8 | WavmCallerLoad16 | WavmCallerLoad32 => {
+                FunctionType::new(vec![I32], vec![I32])
+            }
+            WavmCallerLoad64 => FunctionType::new(vec![I32], vec![I64]),
+            WavmCallerStore8 | WavmCallerStore16 | WavmCallerStore32 => {
+                FunctionType::new(vec![I32, I32], vec![])
+            }
+            WavmCallerStore64 => FunctionType::new(vec![I32, I64], vec![]),
+            WavmCallerLoad8_64 | WavmCallerLoad16_64 | WavmCallerLoad32_64 => {
+                FunctionType::new(vec![I64], vec![I32])
+            }
+            WavmCallerLoad64_64 => FunctionType::new(vec![I64], vec![I64]),
+            WavmCallerStore8_64 | WavmCallerStore16_64 | WavmCallerStore32_64 => {
+                FunctionType::new(vec![I64, I32], vec![])
+            }
+            WavmCallerStore64_64 => FunctionType::new(vec![I64, I64], vec![]),
+            UserGasLeft => FunctionType::new(vec![], vec![I64]),
+            UserGasStatus => FunctionType::new(vec![], vec![I32]),
+            UserGasSet => FunctionType::new(vec![I64, I32], vec![]),
+        }
     }
 }
 
-pub fn get_host_impl(module: &str, name: &str) -> eyre::Result<Function> {
-    let mut out = vec![];
-    let ty;
+/// A hostio's ABI signature paired with the WAVM instruction sequence it lowers to.
+///
+/// Keeping this as iterable data, rather than burying it in a single match, lets a second
+/// consumer (e.g. an out-of-line caller-environment/JIT backend) validate an imported
+/// module's host functions and auto-generate link stubs against the exact same signatures
+/// the prover's WAVM lowering uses, so the two can never drift out of sync.
+struct HostioEntry {
+    module: &'static str,
+    name: &'static str,
+    ty: FunctionType,
+    body: fn(&mut Vec<Instruction>),
+    /// Only resolvable for modules that declare a 64-bit linear memory.
+    memory64_only: bool,
+}
+
+fn hostio_table() -> Vec<HostioEntry> {
+    use ArbValueType::*;
+    use InternalFunc::*;
+    use Opcode::*;
 
     macro_rules! opcode {
-        ($opcode:expr) => {
-            out.push(Instruction::simple($opcode))
+        ($out:expr, $opcode:expr) => {
+            $out.push(Instruction::simple($opcode))
         };
-        ($opcode:expr, $value:expr) => {
-            out.push(Instruction::with_data($opcode, $value as u64))
+        ($out:expr, $opcode:expr, $value:expr) => {
+            $out.push(Instruction::with_data($opcode, $value as u64))
         };
     }
 
-    use ArbValueType::*;
-    use InternalFunc::*;
-    use Opcode::*;
-    match (module, name) {
-        ("env", "wavm_caller_load8") => {
-            ty = FunctionType::new(vec![I32], vec![I32]);
-            opcode!(LocalGet, 0);
-            opcode!(CallerModuleInternalCall, WavmCallerLoad8);
-        }
-        ("env", "wavm_caller_load32") => {
-            ty = FunctionType::new(vec![I32], vec![I32]);
-            opcode!(LocalGet, 0);
-            opcode!(CallerModuleInternalCall, WavmCallerLoad32);
-        }
-        ("env", "wavm_caller_store8") => {
-            ty = FunctionType::new(vec![I32; 2], vec![]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(CallerModuleInternalCall, WavmCallerStore8);
-        }
-        ("env", "wavm_caller_store32") => {
-            ty = FunctionType::new(vec![I32; 2], vec![]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(CallerModuleInternalCall, WavmCallerStore32);
-        }
-        ("env", "wavm_get_globalstate_bytes32") => {
-            ty = FunctionType::new(vec![I32; 2], vec![]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(GetGlobalStateBytes32);
-        }
-        ("env", "wavm_set_globalstate_bytes32") => {
-            ty = FunctionType::new(vec![I32; 2], vec![]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(SetGlobalStateBytes32);
-        }
-        ("env", "wavm_get_globalstate_u64") => {
-            ty = FunctionType::new(vec![I32], vec![I64]);
-            opcode!(LocalGet, 0);
-            opcode!(GetGlobalStateU64);
-        }
-        ("env", "wavm_set_globalstate_u64") => {
-            ty = FunctionType::new(vec![I32, I64], vec![]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(SetGlobalStateU64);
-        }
-        ("env", "wavm_read_pre_image") => {
-            ty = FunctionType::new(vec![I32; 2], vec![I32]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(ReadPreImage);
-        }
-        ("env", "wavm_read_inbox_message") => {
-            ty = FunctionType::new(vec![I64, I32, I32], vec![I32]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(LocalGet, 2);
-            opcode!(ReadInboxMessage, InboxIdentifier::Sequencer);
-        }
-        ("env", "wavm_read_delayed_inbox_message") => {
-            ty = FunctionType::new(vec![I64, I32, I32], vec![I32]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(LocalGet, 2);
-            opcode!(ReadInboxMessage, InboxIdentifier::Delayed);
-        }
-        ("env", "wavm_halt_and_set_finished") => {
-            ty = FunctionType::default();
-            opcode!(HaltAndSetFinished);
-        }
-        ("hostio", "user_gas_left") => {
+    macro_rules! entry {
+        ($module:literal, $name:literal, $params:expr, $returns:expr, $body:expr) => {
+            HostioEntry {
+                module: $module,
+                name: $name,
+                ty: FunctionType::new($params, $returns),
+                body: $body,
+                memory64_only: false,
+            }
+        };
+    }
+
+    // Same as `entry!`, but only resolvable for modules that declare a 64-bit linear
+    // memory: these wavm_caller_*_64 hostios address guest memory with an I64 pointer,
+    // so handing them to a 32-bit-memory module would be nonsensical.
+    macro_rules! entry64 {
+        ($module:literal, $name:literal, $params:expr, $returns:expr, $body:expr) => {
+            HostioEntry {
+                module: $module,
+                name: $name,
+                ty: FunctionType::new($params, $returns),
+                body: $body,
+                memory64_only: true,
+            }
+        };
+    }
+
+    vec![
+        entry!("env", "wavm_caller_load8", vec![I32], vec![I32], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, CallerModuleInternalCall, WavmCallerLoad8);
+        }),
+        entry!("env", "wavm_caller_load32", vec![I32], vec![I32], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, CallerModuleInternalCall, WavmCallerLoad32);
+        }),
+        entry!("env", "wavm_caller_store8", vec![I32; 2], vec![], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, LocalGet, 1);
+            opcode!(out, CallerModuleInternalCall, WavmCallerStore8);
+        }),
+        entry!("env", "wavm_caller_store32", vec![I32; 2], vec![], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, LocalGet, 1);
+            opcode!(out, CallerModuleInternalCall, WavmCallerStore32);
+        }),
+        entry!("env", "wavm_caller_load16", vec![I32], vec![I32], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, CallerModuleInternalCall, WavmCallerLoad16);
+        }),
+        entry!("env", "wavm_caller_load64", vec![I32], vec![I64], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, CallerModuleInternalCall, WavmCallerLoad64);
+        }),
+        entry!("env", "wavm_caller_store16", vec![I32; 2], vec![], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, LocalGet, 1);
+            opcode!(out, CallerModuleInternalCall, WavmCallerStore16);
+        }),
+        entry!(
+            "env",
+            "wavm_caller_store64",
+            vec![I32, I64],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore64);
+            }
+        ),
+        // memory64: same hostios, but addressed with an I64 pointer so wasm modules that
+        // declare a 64-bit linear memory remain provable.
+        entry64!("env", "wavm_caller_load8_64", vec![I64], vec![I32], |out| {
+            opcode!(out, LocalGet, 0);
+            opcode!(out, CallerModuleInternalCall, WavmCallerLoad8_64);
+        }),
+        entry64!(
+            "env",
+            "wavm_caller_load16_64",
+            vec![I64],
+            vec![I32],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerLoad16_64);
+            }
+        ),
+        entry64!(
+            "env",
+            "wavm_caller_load32_64",
+            vec![I64],
+            vec![I32],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerLoad32_64);
+            }
+        ),
+        entry64!(
+            "env",
+            "wavm_caller_load64_64",
+            vec![I64],
+            vec![I64],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerLoad64_64);
+            }
+        ),
+        entry64!(
+            "env",
+            "wavm_caller_store8_64",
+            vec![I64, I32],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore8_64);
+            }
+        ),
+        entry64!(
+            "env",
+            "wavm_caller_store16_64",
+            vec![I64, I32],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore16_64);
+            }
+        ),
+        entry64!(
+            "env",
+            "wavm_caller_store32_64",
+            vec![I64, I32],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore32_64);
+            }
+        ),
+        entry64!(
+            "env",
+            "wavm_caller_store64_64",
+            vec![I64, I64],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore64_64);
+            }
+        ),
+        entry!(
+            "env",
+            "wavm_get_globalstate_bytes32",
+            vec![I32; 2],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, GetGlobalStateBytes32);
+            }
+        ),
+        entry!(
+            "env",
+            "wavm_set_globalstate_bytes32",
+            vec![I32; 2],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, SetGlobalStateBytes32);
+            }
+        ),
+        entry!(
+            "env",
+            "wavm_get_globalstate_u64",
+            vec![I32],
+            vec![I64],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, GetGlobalStateU64);
+            }
+        ),
+        entry!(
+            "env",
+            "wavm_set_globalstate_u64",
+            vec![I32, I64],
+            vec![],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, SetGlobalStateU64);
+            }
+        ),
+        entry!(
+            "env",
+            "wavm_read_pre_image",
+            vec![I32; 2],
+            vec![I32],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, ReadPreImage);
+            }
+        ),
+        entry!(
+            "env",
+            "wavm_read_inbox_message",
+            vec![I64, I32, I32],
+            vec![I32],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, LocalGet, 2);
+                opcode!(out, ReadInboxMessage, InboxIdentifier::Sequencer);
+            }
+        ),
+        entry!(
+            "env",
+            "wavm_read_delayed_inbox_message",
+            vec![I64, I32, I32],
+            vec![I32],
+            |out| {
+                opcode!(out, LocalGet, 0);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, LocalGet, 2);
+                opcode!(out, ReadInboxMessage, InboxIdentifier::Delayed);
+            }
+        ),
+        entry!("env", "wavm_halt_and_set_finished", vec![], vec![], |out| {
+            opcode!(out, HaltAndSetFinished);
+        }),
+        entry!("hostio", "user_gas_left", vec![], vec![I64], |out| {
             // user_gas_left() -> gas_left
-            ty = FunctionType::new(vec![], vec![I64]);
-            opcode!(CallerModuleInternalCall, UserGasLeft);
-        }
-        ("hostio", "user_gas_status") => {
+            opcode!(out, CallerModuleInternalCall, UserGasLeft);
+        }),
+        entry!("hostio", "user_gas_status", vec![], vec![I32], |out| {
             // user_gas_status() -> gas_status
-            ty = FunctionType::new(vec![], vec![I32]);
-            opcode!(CallerModuleInternalCall, UserGasStatus);
-        }
-        ("hostio", "user_set_gas") => {
+            opcode!(out, CallerModuleInternalCall, UserGasStatus);
+        }),
+        entry!("hostio", "user_set_gas", vec![I64, I32], vec![], |out| {
             // user_set_gas(gas_left, gas_status)
-            ty = FunctionType::new(vec![I64, I32], vec![]);
-            opcode!(LocalGet, 0);
-            opcode!(LocalGet, 1);
-            opcode!(CallerModuleInternalCall, UserGasSet);
-        }
-        _ => eyre::bail!("no such hostio {} in {}", name.red(), module.red()),
-    }
+            opcode!(out, LocalGet, 0);
+            opcode!(out, LocalGet, 1);
+            opcode!(out, CallerModuleInternalCall, UserGasSet);
+        }),
+        // WASI preview1 stubs. These exist so that wasm32-wasi guests can be used as replay
+        // inputs without a patched wasi-stub library. Every stub is fully deterministic: no
+        // host clock, no OS entropy, and no dependence on anything but global state and the
+        // preimage oracle, so that two provers executing the same module agree byte-for-byte.
+        entry!(
+            "wasi_snapshot_preview1",
+            "proc_exit",
+            vec![I32],
+            vec![],
+            |out| {
+                // proc_exit(code: i32)
+                opcode!(out, HaltAndSetFinished);
+            }
+        ),
+        entry!(
+            "wasi_snapshot_preview1",
+            "fd_write",
+            vec![I32; 4],
+            vec![I32],
+            |out| {
+                // fd_write(fd, iovs, iovs_len, nwritten) -> errno
+                // Scoped stub: reads (and discards) at most one byte, the first byte of the
+                // first iovec's buffer only (no iovec-array walk; that iovec's own buf_len
+                // is not checked). `iovs_len == 0` is a real wasi-libc call shape (e.g. a
+                // flush with nothing buffered), so it's treated as a no-op rather than
+                // dereferencing `iovs` unconditionally — doing so unconditionally would fault
+                // on that otherwise-valid input. `nwritten` reflects whether a byte was
+                // actually read (0 or 1), not a hardcoded count.
+                opcode!(out, LocalGet, 2); // iovs_len
+                opcode!(out, I32Eqz);
+                let skip_copy = out.len();
+                opcode!(out, ArbitraryJumpIf, 0); // patched below
+                opcode!(out, LocalGet, 1);
+                opcode!(out, CallerModuleInternalCall, WavmCallerLoad32); // iovs[0].buf
+                opcode!(out, CallerModuleInternalCall, WavmCallerLoad8);
+                opcode!(out, Drop);
+                let after_copy = out.len() as u64;
+                out[skip_copy] = Instruction::with_data(ArbitraryJumpIf, after_copy);
+                opcode!(out, LocalGet, 3); // nwritten
+                opcode!(out, LocalGet, 2); // iovs_len != 0 as 0/1
+                opcode!(out, I32Eqz);
+                opcode!(out, I32Eqz);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore32);
+                opcode!(out, I32Const, 0);
+            }
+        ),
+        entry!(
+            "wasi_snapshot_preview1",
+            "fd_read",
+            vec![I32; 4],
+            vec![I32],
+            |out| {
+                // fd_read(fd, iovs, iovs_len, nread) -> errno
+                // Mirrors fd_write's scoping: stores at most one deterministic byte (always
+                // zero) into the first byte of the first iovec's buffer only (that iovec's
+                // own buf_len is not checked). `iovs_len == 0`
+                // short-circuits before touching `iovs` at all, for the same reason — a
+                // guest legitimately passing no buffers must not fault. `nread` reflects
+                // whether a byte was actually stored (0 or 1).
+                opcode!(out, LocalGet, 2); // iovs_len
+                opcode!(out, I32Eqz);
+                let skip_store = out.len();
+                opcode!(out, ArbitraryJumpIf, 0); // patched below
+                opcode!(out, LocalGet, 1);
+                opcode!(out, CallerModuleInternalCall, WavmCallerLoad32); // iovs[0].buf
+                opcode!(out, I32Const, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore8);
+                let after_store = out.len() as u64;
+                out[skip_store] = Instruction::with_data(ArbitraryJumpIf, after_store);
+                opcode!(out, LocalGet, 3); // nread
+                opcode!(out, LocalGet, 2); // iovs_len != 0 as 0/1
+                opcode!(out, I32Eqz);
+                opcode!(out, I32Eqz);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore32);
+                opcode!(out, I32Const, 0);
+            }
+        ),
+        entry!(
+            "wasi_snapshot_preview1",
+            "clock_time_get",
+            vec![I32, I64, I32],
+            vec![I32],
+            |out| {
+                // clock_time_get(clock_id, precision, time_out) -> errno
+                // Reads a deterministic timestamp out of global state (GetGlobalStateU64),
+                // ignoring `clock_id`/`precision`, and stores the full 64-bit value to
+                // `time_out` through WavmCallerStore64 (the field is a u64 nanosecond
+                // count; storing fewer than 8 bytes would leave the high bytes as
+                // whatever garbage already sits in guest memory).
+                opcode!(out, LocalGet, 2);
+                opcode!(out, I32Const, 0);
+                opcode!(out, GetGlobalStateU64);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore64);
+                opcode!(out, I32Const, 0);
+            }
+        ),
+        entry!(
+            "wasi_snapshot_preview1",
+            "args_sizes_get",
+            vec![I32; 2],
+            vec![I32],
+            |out| {
+                // args_sizes_get(argc_out, argv_buf_size_out) -> errno
+                // Reports a fixed, deterministic argv of zero arguments. Both out-params
+                // are full i32 fields, so they're zeroed with WavmCallerStore32 rather
+                // than a single truncating byte store.
+                opcode!(out, LocalGet, 0);
+                opcode!(out, I32Const, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore32);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, I32Const, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore32);
+                opcode!(out, I32Const, 0);
+            }
+        ),
+        entry!(
+            "wasi_snapshot_preview1",
+            "environ_sizes_get",
+            vec![I32; 2],
+            vec![I32],
+            |out| {
+                // environ_sizes_get(environc_out, environ_buf_size_out) -> errno
+                // Reports a fixed, deterministic environment of zero variables. Both
+                // out-params are full i32 fields, so they're zeroed with
+                // WavmCallerStore32 rather than a single truncating byte store.
+                opcode!(out, LocalGet, 0);
+                opcode!(out, I32Const, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore32);
+                opcode!(out, LocalGet, 1);
+                opcode!(out, I32Const, 0);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore32);
+                opcode!(out, I32Const, 0);
+            }
+        ),
+        entry!(
+            "wasi_snapshot_preview1",
+            "random_get",
+            vec![I32; 2],
+            vec![I32],
+            |out| {
+                // random_get(buf, buf_len) -> errno
+                // Scoped stub: writes the u64 global-state value at index 0 (read via
+                // GetGlobalStateU64, the same value clock_time_get reports) into the first
+                // 8 bytes of the target buffer through WavmCallerStore64, rather than any
+                // real entropy source. Every call writes that same value, and no preimage
+                // is read. `buf_len` is not checked: a buffer shorter than 8 bytes is
+                // overrun, and bytes past the first 8 are left unchanged.
+                opcode!(out, LocalGet, 0);
+                opcode!(out, I32Const, 0);
+                opcode!(out, GetGlobalStateU64);
+                opcode!(out, CallerModuleInternalCall, WavmCallerStore64);
+                opcode!(out, I32Const, 0);
+            }
+        ),
+    ]
+}
+
+/// Iterates every hostio's `(module, name, FunctionType)`, so a second consumer (e.g. a
+/// native/JIT caller-environment backend) can validate an imported module's host functions
+/// or auto-generate link stubs without drifting out of sync with `get_host_impl`'s WAVM
+/// lowering.
+pub fn host_signatures() -> impl Iterator<Item = (&'static str, &'static str, FunctionType)> {
+    hostio_table()
+        .into_iter()
+        .map(|entry| (entry.module, entry.name, entry.ty))
+}
+
+pub fn get_host_impl(module: &str, name: &str, memory64: bool) -> eyre::Result<Function> {
+    let entry = hostio_table()
+        .into_iter()
+        .find(|entry| entry.module == module && entry.name == name)
+        .filter(|entry| memory64 || !entry.memory64_only)
+        .ok_or_else(|| eyre::eyre!("no such hostio {} in {}", name.red(), module.red()))?;
+
+    let mut out = vec![];
+    (entry.body)(&mut out);
 
-    let append = |code: &mut Vec<Instruction>| {
-        code.extend(out);
+    let append = move |code: &mut Vec<Instruction>| {
+        // Jump targets in a body are indices into `out`; rebase them past whatever the
+        // function already holds ahead of the body.
+        let base = code.len() as u64;
+        code.extend(out.into_iter().map(|mut inst| {
+            if inst.opcode == Opcode::ArbitraryJumpIf {
+                inst.argument_data += base;
+            }
+            inst
+        }));
         Ok(())
     };
 
-    Function::new(&[], append, ty, &[])
+    Function::new(&[], append, entry.ty, &[])
 }
 
 /// Adds internal functions to a module.
-/// Note: the order of the functions must match that of the `InternalFunc` enum
+/// Note: the order of the functions must match that of the `InternalFunc` enum.
+/// `memory64` gates the `_64` variants: they're pushed for modules that declare a 64-bit
+/// linear memory, and also for modules with Stylus globals, where they only hold the slots
+/// ahead of the UserGas* functions.
 pub fn add_internal_funcs(
     funcs: &mut Vec<Function>,
     func_types: &mut Vec<FunctionType>,
     globals: Option<StylusGlobals>,
+    memory64: bool,
 ) {
     use ArbValueType::*;
     use InternalFunc::*;
@@ -196,6 +591,93 @@ pub fn add_internal_funcs(
         host(WavmCallerStore32),
     ));
 
+    // 32-bit-addressed, wider accesses
+    funcs.push(op_func(
+        MemoryLoad {
+            ty: I32,
+            bytes: 2,
+            signed: false,
+        },
+        host(WavmCallerLoad16),
+    ));
+    funcs.push(op_func(
+        MemoryLoad {
+            ty: I64,
+            bytes: 8,
+            signed: false,
+        },
+        host(WavmCallerLoad64),
+    ));
+    funcs.push(op_func(
+        MemoryStore { ty: I32, bytes: 2 },
+        host(WavmCallerStore16),
+    ));
+    funcs.push(op_func(
+        MemoryStore { ty: I64, bytes: 8 },
+        host(WavmCallerStore64),
+    ));
+
+    // memory64: same widths, addressed with an I64 pointer instead of I32. Pushed for
+    // memory64 modules, and also whenever the Stylus block below follows, so that the
+    // UserGas* functions keep the indices of their enum discriminants. A module with
+    // neither doesn't carry eight internal-function slots it can never reach (its imports
+    // can't name the memory64_only hostios that target them — see `get_host_impl`).
+    if memory64 || globals.is_some() {
+        funcs.push(op_func(
+            MemoryLoad {
+                ty: I32,
+                bytes: 1,
+                signed: false,
+            },
+            host(WavmCallerLoad8_64),
+        ));
+        funcs.push(op_func(
+            MemoryLoad {
+                ty: I32,
+                bytes: 2,
+                signed: false,
+            },
+            host(WavmCallerLoad16_64),
+        ));
+        funcs.push(op_func(
+            MemoryLoad {
+                ty: I32,
+                bytes: 4,
+                signed: false,
+            },
+            host(WavmCallerLoad32_64),
+        ));
+        funcs.push(op_func(
+            MemoryLoad {
+                ty: I64,
+                bytes: 8,
+                signed: false,
+            },
+            host(WavmCallerLoad64_64),
+        ));
+        funcs.push(op_func(
+            MemoryStore { ty: I32, bytes: 1 },
+            host(WavmCallerStore8_64),
+        ));
+        funcs.push(op_func(
+            MemoryStore { ty: I32, bytes: 2 },
+            host(WavmCallerStore16_64),
+        ));
+        funcs.push(op_func(
+            MemoryStore { ty: I32, bytes: 4 },
+            host(WavmCallerStore32_64),
+        ));
+        funcs.push(op_func(
+            MemoryStore { ty: I64, bytes: 8 },
+            host(WavmCallerStore64_64),
+        ));
+    }
+
+    // Stylus gas metering internals come last: they're only present when a module carries
+    // Stylus globals, so anything pushed after this block would otherwise land at the wrong
+    // index (and thus the wrong `CallerModuleInternalCall` target) whenever `globals` is
+    // `None` — which is the common case for non-Stylus modules, including the WASI and
+    // memory64 guests the internal functions above exist for.
     if let Some(globals) = globals {
         let (gas, status) = globals.offsets();
         funcs.push(code_func(
@@ -215,3 +697,72 @@ pub fn add_internal_funcs(
         ));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn internal_func_discriminants_match_push_order_without_stylus_globals() {
+        // Without Stylus globals, the UserGas* internal functions aren't pushed at all, so
+        // every other `InternalFunc` must keep the same index as its enum discriminant —
+        // that index is exactly what `CallerModuleInternalCall` dispatches on.
+        use InternalFunc::*;
+        let without_stylus_globals = [
+            WavmCallerLoad8,
+            WavmCallerLoad32,
+            WavmCallerStore8,
+            WavmCallerStore32,
+            WavmCallerLoad16,
+            WavmCallerLoad64,
+            WavmCallerStore16,
+            WavmCallerStore64,
+            WavmCallerLoad8_64,
+            WavmCallerLoad16_64,
+            WavmCallerLoad32_64,
+            WavmCallerLoad64_64,
+            WavmCallerStore8_64,
+            WavmCallerStore16_64,
+            WavmCallerStore32_64,
+            WavmCallerStore64_64,
+        ];
+
+        let mut funcs = Vec::new();
+        let mut func_types = Vec::new();
+        add_internal_funcs(&mut funcs, &mut func_types, None, true);
+
+        assert_eq!(funcs.len(), without_stylus_globals.len());
+        assert_eq!(func_types.len(), without_stylus_globals.len());
+        for (index, variant) in without_stylus_globals.iter().enumerate() {
+            assert_eq!(
+                *variant as usize, index,
+                "enum discriminant must match push position"
+            );
+            assert_eq!(
+                func_types[index],
+                variant.ty(),
+                "func_types[{index}] must be the signature add_internal_funcs pushed for this discriminant"
+            );
+        }
+    }
+
+    #[test]
+    fn memory64_internal_funcs_are_gated_on_the_memory64_flag() {
+        // A non-memory64 module without Stylus globals must not carry the eight `_64`
+        // internal-function slots:
+        // it has no way to reach them, since `get_host_impl` won't hand out the
+        // memory64_only hostios that target them.
+        let mut funcs = Vec::new();
+        let mut func_types = Vec::new();
+        add_internal_funcs(&mut funcs, &mut func_types, None, false);
+
+        assert_eq!(funcs.len(), 8);
+        assert_eq!(func_types.len(), 8);
+    }
+
+    #[test]
+    fn memory64_only_hostios_are_unresolvable_without_the_memory64_flag() {
+        assert!(get_host_impl("env", "wavm_caller_load8_64", false).is_err());
+        assert!(get_host_impl("env", "wavm_caller_load8_64", true).is_ok());
+    }
+}